@@ -1,4 +1,4 @@
-use {DenseMatrix, Element};
+use {Dense, Element};
 
 /// A band matrix.
 ///
@@ -22,35 +22,35 @@ pub struct BandMatrix<T> {
     pub data: Vec<T>,
 }
 
-impl<T> From<BandMatrix<T>> for DenseMatrix<T> where T: Element {
-    fn from(band: BandMatrix<T>) -> DenseMatrix<T> {
+impl<T> From<BandMatrix<T>> for Dense<T> where T: Element {
+    fn from(band: BandMatrix<T>) -> Dense<T> {
         let BandMatrix { rows, columns, superdiagonals, subdiagonals, ref data } = band;
 
         let diagonals = superdiagonals + 1 + subdiagonals;
         debug_assert_eq!(data.len(), diagonals * columns);
 
-        let mut dense = DenseMatrix {
+        let mut dense = Dense {
             rows: rows,
             columns: columns,
-            data: vec![Element::zero(); rows * columns],
+            values: vec![Element::zero(); rows * columns],
         };
 
         for k in 1..(superdiagonals + 1) {
             for j in k..columns {
                 let i = j - k;
                 if i >= rows { break; }
-                dense.data[j * rows + i] = data[j * diagonals + superdiagonals - k];
+                dense.values[j * rows + i] = data[j * diagonals + superdiagonals - k];
             }
         }
         for i in 0..columns {
             if i >= rows || i >= columns { break; }
-            dense.data[i * rows + i] = data[i * diagonals + superdiagonals];
+            dense.values[i * rows + i] = data[i * diagonals + superdiagonals];
         }
         for k in 1..(subdiagonals + 1) {
             for j in 0..columns {
                 let i = j + k;
                 if i >= rows { break; }
-                dense.data[j * rows + i] = data[j * diagonals + superdiagonals + k];
+                dense.values[j * rows + i] = data[j * diagonals + superdiagonals + k];
             }
         }
 
@@ -60,7 +60,7 @@ impl<T> From<BandMatrix<T>> for DenseMatrix<T> where T: Element {
 
 #[cfg(test)]
 mod tests {
-    use {BandMatrix, DenseMatrix};
+    use {BandMatrix, Dense};
 
     #[test]
     fn into_tall_dense() {
@@ -77,9 +77,9 @@ mod tests {
             ],
         };
 
-        let dense: DenseMatrix<f64> = band.into();
+        let dense: Dense<f64> = band.into();
 
-        assert_eq!(&dense[..], &[
+        assert_eq!(&*dense, &[
             1.0, 4.0,  8.0,  0.0,  0.0,  0.0, 0.0,
             2.0, 5.0,  9.0, 12.0,  0.0,  0.0, 0.0,
             3.0, 6.0, 10.0, 13.0, 15.0,  0.0, 0.0,
@@ -105,9 +105,9 @@ mod tests {
             ],
         };
 
-        let dense: DenseMatrix<f64> = band.into();
+        let dense: Dense<f64> = band.into();
 
-        assert_eq!(&dense[..], &[
+        assert_eq!(&*dense, &[
             1.0, 4.0,  8.0,  0.0,
             2.0, 5.0,  9.0, 13.0,
             3.0, 6.0, 10.0, 14.0,
@@ -8,10 +8,18 @@
 //!
 //! [1]: http://netlib.org/linalg/html_templates/node92.html
 //! [2]: http://netlib.org/linalg/html_templates/node91.html
-
+//!
+//! `Add`, `Sub`, and `Mul` are implemented for `Compressed`-`Compressed` and
+//! `Compressed`-`Dense` pairs, plus scalar scaling via `Mul<T>`. There is no
+//! `Diagonal` type in this tree to permute against (the crate's only
+//! `DiagonalMatrix`, in `diagonal.rs`, is a different, unwired type from an
+//! earlier era of this crate and is not interchangeable with `Compressed`),
+//! so those permutations are not covered here.
+
+use std::ops::{Add, Mul, Sub};
 use std::{iter, mem};
 
-use {Dense, Element, Matrix, Position, Size};
+use {Dense, Element, Matrix, Number, Position, Size};
 
 /// A compressed matrix.
 #[derive(Clone, Debug, PartialEq)]
@@ -161,6 +169,56 @@ impl<T: Element> Compressed<T> {
         self.rows = rows;
     }
 
+    /// Convert the storage between the compressed-column and
+    /// compressed-row formats in place.
+    ///
+    /// This runs in O(nnz + rows + columns) using the standard
+    /// CSC<->CSR transpose-by-counting algorithm: entries are histogrammed
+    /// per target major into a fresh `offsets` array, prefix-summed, and
+    /// then scattered into their final slot using a per-major running
+    /// cursor, which naturally produces sorted indices within each new
+    /// major.
+    pub fn into_format(&mut self, target: Format) {
+        if self.format == target {
+            return;
+        }
+
+        let target_majors = match target {
+            Format::Column => self.columns,
+            Format::Row => self.rows,
+        };
+        let source_majors = match self.format {
+            Format::Column => self.columns,
+            Format::Row => self.rows,
+        };
+
+        let mut offsets = vec![0; target_majors + 1];
+        for &index in &self.indices {
+            offsets[index + 1] += 1;
+        }
+        for major in 0..target_majors {
+            offsets[major + 1] += offsets[major];
+        }
+
+        let mut cursor = offsets.clone();
+        let mut indices = vec![0; self.nonzeros];
+        let mut values = vec![T::zero(); self.nonzeros];
+        for major in 0..source_majors {
+            for k in self.offsets[major]..self.offsets[major + 1] {
+                let target_major = self.indices[k];
+                let slot = cursor[target_major];
+                indices[slot] = major;
+                values[slot] = self.values[k];
+                cursor[target_major] += 1;
+            }
+        }
+
+        self.format = target;
+        self.values = values;
+        self.indices = indices;
+        self.offsets = offsets;
+    }
+
     /// Retain the elements that satisfy a condition and discard the rest.
     pub fn retain<F>(&mut self, mut condition: F) where F: FnMut(usize, usize, &T) -> bool {
         let (mut k, mut major) = (0, 0);
@@ -194,12 +252,16 @@ impl<T: Element> Matrix for Compressed<T> {
     }
 
     fn transpose(&mut self) {
-        let &mut Compressed { rows, columns, nonzeros, format, .. } = self;
-        let mut matrix = Compressed::with_capacity((columns, rows), format, nonzeros);
-        for (i, j, &value) in self.iter() {
-            matrix.set((j, i), value);
-        }
-        *self = matrix;
+        // The compressed-row storage of `A` is exactly the compressed-column
+        // storage of `A^T`, so swapping the dimensions and flipping the
+        // format label reinterprets the existing storage as the transpose
+        // for free; `into_format` then does the one O(nnz) pass needed to
+        // convert it back to the original format, leaving the format
+        // unchanged from the caller's perspective.
+        let format = self.format;
+        mem::swap(&mut self.rows, &mut self.columns);
+        self.format = self.format.flip();
+        self.into_format(format);
     }
 
     #[inline]
@@ -271,6 +333,222 @@ impl Format {
     }
 }
 
+impl<T: Element + Number> Add<Dense<T>> for Compressed<T> {
+    type Output = Dense<T>;
+
+    /// Add a sparse matrix to a dense one.
+    ///
+    /// The result is necessarily dense, so the nonzeros of `self` are
+    /// scattered directly into a clone of `rhs` rather than densifying
+    /// `self` first.
+    fn add(self, rhs: Dense<T>) -> Dense<T> {
+        debug_assert_eq!(self.rows, rhs.rows);
+        debug_assert_eq!(self.columns, rhs.columns);
+
+        let Dense { rows, columns, mut values } = rhs;
+        for (i, j, &value) in self.iter() {
+            values[j * rows + i] = values[j * rows + i] + value;
+        }
+
+        Dense { rows: rows, columns: columns, values: values }
+    }
+}
+
+impl<T: Element + Number> Sub<Dense<T>> for Compressed<T> {
+    type Output = Dense<T>;
+
+    /// Subtract a dense matrix from a sparse one; see `Add`.
+    fn sub(self, rhs: Dense<T>) -> Dense<T> {
+        debug_assert_eq!(self.rows, rhs.rows);
+        debug_assert_eq!(self.columns, rhs.columns);
+
+        let Dense { rows, columns, values: rhs_values } = rhs;
+        let mut values: Vec<T> = rhs_values.into_iter().map(|value| T::zero() - value).collect();
+        for (i, j, &value) in self.iter() {
+            values[j * rows + i] = values[j * rows + i] + value;
+        }
+
+        Dense { rows: rows, columns: columns, values: values }
+    }
+}
+
+impl<T: Element + Number> Mul<Dense<T>> for Compressed<T> {
+    type Output = Dense<T>;
+
+    /// Multiply a sparse matrix by a dense one.
+    ///
+    /// Unlike the sparse-sparse `Mul`, the result is necessarily dense, so
+    /// each nonzero of `self` is scattered directly into the output columns
+    /// rather than through the seen/accumulator workspace that the
+    /// sparse-sparse `Mul` needs to keep its own output sparse.
+    fn mul(self, rhs: Dense<T>) -> Dense<T> {
+        debug_assert_eq!(self.columns, rhs.rows);
+
+        let (m, p, n) = (self.rows, self.columns, rhs.columns);
+        let mut values = vec![T::zero(); m * n];
+        for (i, k, &value) in self.iter() {
+            for j in 0..n {
+                values[j * m + i] = values[j * m + i] + value * rhs.values[j * p + k];
+            }
+        }
+
+        Dense { rows: m, columns: n, values: values }
+    }
+}
+
+impl<T: Element + Number> Add for Compressed<T> {
+    type Output = Self;
+
+    /// Add two matrices of the same format.
+    ///
+    /// The sorted index runs of each major are merged in a single linear
+    /// pass, so the result costs O(nnz(self) + nnz(rhs)) rather than
+    /// densifying first. Entries that cancel out exactly are dropped.
+    fn add(self, rhs: Self) -> Self {
+        merge(&self, &rhs, |a, b| a + b, |b| b)
+    }
+}
+
+impl<T: Element + Number> Sub for Compressed<T> {
+    type Output = Self;
+
+    /// Subtract two matrices of the same format; see `Add`.
+    fn sub(self, rhs: Self) -> Self {
+        merge(&self, &rhs, |a, b| a - b, |b| T::zero() - b)
+    }
+}
+
+impl<T: Element + Number> Mul for Compressed<T> {
+    type Output = Self;
+
+    /// Multiply two sparse matrices using Gustavson's algorithm.
+    ///
+    /// For each column `j` of `rhs`, the nonzeros of the corresponding
+    /// columns of `self` are accumulated into a dense scatter workspace of
+    /// length `self.rows` guarded by a "seen" marker array, so every output
+    /// row is touched at most once per column before being gathered in
+    /// sorted order. This keeps the product at roughly O(flops) rather than
+    /// O(rows * columns).
+    fn mul(self, rhs: Self) -> Self {
+        debug_assert_eq!(self.format, Format::Column);
+        debug_assert_eq!(rhs.format, Format::Column);
+        debug_assert_eq!(self.columns, rhs.rows);
+
+        let (m, n) = (self.rows, rhs.columns);
+        let mut values = Vec::new();
+        let mut indices = Vec::new();
+        let mut offsets = Vec::with_capacity(n + 1);
+        offsets.push(0);
+
+        let mut accumulator = vec![T::zero(); m];
+        let mut seen = vec![false; m];
+        let mut touched = Vec::new();
+
+        for j in 0..n {
+            touched.clear();
+            for k in rhs.offsets[j]..rhs.offsets[j + 1] {
+                let l = rhs.indices[k];
+                let b_lj = rhs.values[k];
+                for p in self.offsets[l]..self.offsets[l + 1] {
+                    let i = self.indices[p];
+                    if !seen[i] {
+                        seen[i] = true;
+                        touched.push(i);
+                    }
+                    accumulator[i] = accumulator[i] + self.values[p] * b_lj;
+                }
+            }
+            touched.sort();
+            for &i in &touched {
+                if !accumulator[i].is_zero() {
+                    indices.push(i);
+                    values.push(accumulator[i]);
+                }
+                accumulator[i] = T::zero();
+                seen[i] = false;
+            }
+            offsets.push(values.len());
+        }
+
+        let nonzeros = values.len();
+        Compressed { rows: m, columns: n, nonzeros, format: Format::Column, values, indices, offsets }
+    }
+}
+
+impl<T: Element + Number> Mul<T> for Compressed<T> {
+    type Output = Self;
+
+    /// Scale every nonzero by a scalar; the sparsity pattern is unchanged.
+    fn mul(self, rhs: T) -> Self {
+        let Compressed { rows, columns, nonzeros, format, values, indices, offsets } = self;
+        let values = values.into_iter().map(|value| value * rhs).collect();
+        Compressed { rows, columns, nonzeros, format, values, indices, offsets }
+    }
+}
+
+/// Merge the sorted index runs of each major of two same-format matrices,
+/// combining coincident indices with `combine` and mapping `rhs`-only
+/// entries through `only_rhs`. Used to implement `Add` and `Sub`.
+fn merge<T, F, G>(lhs: &Compressed<T>, rhs: &Compressed<T>, combine: F, only_rhs: G) -> Compressed<T>
+where
+    T: Element,
+    F: Fn(T, T) -> T,
+    G: Fn(T) -> T,
+{
+    debug_assert_eq!(lhs.rows, rhs.rows);
+    debug_assert_eq!(lhs.columns, rhs.columns);
+    debug_assert_eq!(lhs.format, rhs.format);
+
+    let majors = match lhs.format {
+        Format::Column => lhs.columns,
+        Format::Row => lhs.rows,
+    };
+
+    let mut values = Vec::with_capacity(lhs.nonzeros + rhs.nonzeros);
+    let mut indices = Vec::with_capacity(lhs.nonzeros + rhs.nonzeros);
+    let mut offsets = Vec::with_capacity(majors + 1);
+    offsets.push(0);
+
+    for major in 0..majors {
+        let (mut p, mut q) = (lhs.offsets[major], rhs.offsets[major]);
+        let (pend, qend) = (lhs.offsets[major + 1], rhs.offsets[major + 1]);
+        while p < pend && q < qend {
+            let (i, j) = (lhs.indices[p], rhs.indices[q]);
+            if i < j {
+                values.push(lhs.values[p]);
+                indices.push(i);
+                p += 1;
+            } else if i > j {
+                values.push(only_rhs(rhs.values[q]));
+                indices.push(j);
+                q += 1;
+            } else {
+                let value = combine(lhs.values[p], rhs.values[q]);
+                if !value.is_zero() {
+                    values.push(value);
+                    indices.push(i);
+                }
+                p += 1;
+                q += 1;
+            }
+        }
+        while p < pend {
+            values.push(lhs.values[p]);
+            indices.push(lhs.indices[p]);
+            p += 1;
+        }
+        while q < qend {
+            values.push(only_rhs(rhs.values[q]));
+            indices.push(rhs.indices[q]);
+            q += 1;
+        }
+        offsets.push(values.len());
+    }
+
+    let nonzeros = values.len();
+    Compressed { rows: lhs.rows, columns: lhs.columns, nonzeros, format: lhs.format, values, indices, offsets }
+}
+
 impl<'l, T: Element> iter::Iterator for Iterator<'l, T> {
     type Item = (usize, usize, &'l T);
 
@@ -463,6 +741,102 @@ mod tests {
                                 vec![1, 3, 4, 4], vec![0, 1, 3, 4]));
     }
 
+    #[test]
+    fn into_format() {
+        let mut matrix = new!(5, 7, 5, Format::Column, vec![1.0, 2.0, 3.0, 4.0, 5.0],
+                              vec![1, 0, 3, 1, 4], vec![0, 0, 0, 1, 2, 2, 3, 5]);
+
+        matrix.into_format(Format::Row);
+        assert_eq!(matrix.format, Format::Row);
+
+        let dense_before: Dense<_> = (&matrix).into();
+        matrix.into_format(Format::Column);
+        assert_eq!(matrix.format, Format::Column);
+        let dense_after: Dense<_> = (&matrix).into();
+
+        assert_eq!(dense_before, dense_after);
+        assert_eq!(matrix, new!(5, 7, 5, Format::Column, vec![1.0, 2.0, 3.0, 4.0, 5.0],
+                                vec![1, 0, 3, 1, 4], vec![0, 0, 0, 1, 2, 2, 3, 5]));
+    }
+
+    #[test]
+    fn add() {
+        let a = new!(2, 2, 2, Format::Column, vec![1.0, 2.0], vec![0, 1], vec![0, 1, 2]);
+        let b = new!(2, 2, 2, Format::Column, vec![3.0, 4.0], vec![1, 0], vec![0, 1, 2]);
+
+        let c = a + b;
+
+        let dense: Dense<_> = (&c).into();
+        assert_eq!(&*dense, &[1.0, 3.0, 4.0, 2.0]);
+    }
+
+    #[test]
+    fn sub_cancels_exact_zeros() {
+        let a = new!(2, 2, 1, Format::Column, vec![5.0], vec![0], vec![0, 1, 1]);
+        let b = new!(2, 2, 1, Format::Column, vec![5.0], vec![0], vec![0, 1, 1]);
+
+        let c = a - b;
+        assert_eq!(c.nonzeros, 0);
+    }
+
+    #[test]
+    fn mul() {
+        let dense_a = Dense::from_vec(vec![
+            1.0, 0.0,
+            0.0, 2.0,
+        ], (2, 2));
+        let dense_b = Dense::from_vec(vec![
+            0.0, 1.0,
+            1.0, 0.0,
+        ], (2, 2));
+
+        let a: Compressed<_> = dense_a.clone().into();
+        let b: Compressed<_> = dense_b.clone().into();
+
+        let c = a * b;
+        let dense: Dense<_> = (&c).into();
+        assert_eq!(&*dense, &[0.0, 2.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn add_dense() {
+        let a = new!(2, 2, 2, Format::Column, vec![1.0, 2.0], vec![0, 1], vec![0, 1, 2]);
+        let b = Dense::from_vec(vec![0.0, 4.0, 3.0, 0.0], (2, 2));
+
+        let c = a + b;
+
+        assert_eq!(&*c, &[1.0, 4.0, 3.0, 2.0]);
+    }
+
+    #[test]
+    fn sub_dense() {
+        let a = new!(2, 2, 1, Format::Column, vec![5.0], vec![0], vec![0, 1, 1]);
+        let b = Dense::from_vec(vec![5.0, 0.0, 0.0, 1.0], (2, 2));
+
+        let c = a - b;
+
+        assert_eq!(&*c, &[0.0, 0.0, 0.0, -1.0]);
+    }
+
+    #[test]
+    fn mul_dense() {
+        let a = new!(2, 2, 2, Format::Column, vec![1.0, 2.0], vec![0, 1], vec![0, 1, 2]);
+        let b = Dense::from_vec(vec![0.0, 1.0, 1.0, 0.0], (2, 2));
+
+        let c = a * b;
+
+        assert_eq!(&*c, &[0.0, 2.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn mul_scalar() {
+        let a = new!(2, 2, 2, Format::Column, vec![1.0, 2.0], vec![0, 1], vec![0, 1, 2]);
+
+        let c = a * 3.0;
+
+        assert_eq!(c, new!(2, 2, 2, Format::Column, vec![3.0, 6.0], vec![0, 1], vec![0, 1, 2]));
+    }
+
     #[test]
     fn into_dense() {
         let matrix = new!(5, 3, 3, Format::Column, vec![1.0, 2.0, 3.0],
@@ -0,0 +1,237 @@
+//! Coordinate matrices.
+//!
+//! The storage is suitable for assembling a sparse matrix entry by entry,
+//! e.g. from a finite-element stencil, before converting it once into a
+//! [`Compressed`] matrix for further processing.
+
+use compressed::Format;
+use {Compressed, Element, Matrix, Number, Size};
+
+/// A coordinate matrix.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Coo<T: Element> {
+    /// The number of rows.
+    pub rows: usize,
+    /// The number of columns.
+    pub columns: usize,
+    /// The row indices of the nonzero elements.
+    pub row_indices: Vec<usize>,
+    /// The column indices of the nonzero elements.
+    pub col_indices: Vec<usize>,
+    /// The values of the nonzero elements.
+    pub values: Vec<T>,
+}
+
+size!(Coo);
+
+impl<T: Element> Coo<T> {
+    /// Create an empty matrix.
+    #[inline]
+    pub fn new<S: Size>(size: S) -> Self {
+        Coo::with_capacity(size, 0)
+    }
+
+    /// Create an empty matrix with a specific capacity.
+    pub fn with_capacity<S: Size>(size: S, capacity: usize) -> Self {
+        let (rows, columns) = size.dimensions();
+        Coo {
+            rows: rows,
+            columns: columns,
+            row_indices: Vec::with_capacity(capacity),
+            col_indices: Vec::with_capacity(capacity),
+            values: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Append a nonzero triplet.
+    ///
+    /// The function merely appends to the underlying vectors; no search or
+    /// shift is performed, which is what makes the format cheap to build
+    /// incrementally. Repeated `(i, j)` pairs are allowed and are summed
+    /// together when the matrix is converted into a `Compressed` matrix.
+    #[inline]
+    pub fn push(&mut self, i: usize, j: usize, value: T) {
+        debug_assert!(i < self.rows && j < self.columns);
+        self.row_indices.push(i);
+        self.col_indices.push(j);
+        self.values.push(value);
+    }
+
+    /// Return the number of stored triplets, including duplicates.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+}
+
+impl<'l, T: Element + Number> From<&'l Coo<T>> for Compressed<T> {
+    fn from(coo: &'l Coo<T>) -> Self {
+        let &Coo { rows, columns, ref row_indices, ref col_indices, ref values } = coo;
+        let nonzeros = values.len();
+
+        // Counting sort: bucket each triplet into its target column in one
+        // linear pass over the triplets plus one over the columns.
+        let mut offsets = vec![0; columns + 1];
+        for &j in col_indices {
+            offsets[j + 1] += 1;
+        }
+        for j in 0..columns {
+            offsets[j + 1] += offsets[j];
+        }
+
+        let mut cursor = offsets.clone();
+        let mut indices = vec![0; nonzeros];
+        let mut sorted = vec![T::zero(); nonzeros];
+        for k in 0..nonzeros {
+            let j = col_indices[k];
+            let slot = cursor[j];
+            indices[slot] = row_indices[k];
+            sorted[slot] = values[k];
+            cursor[j] += 1;
+        }
+
+        let mut matrix = Compressed {
+            rows: rows,
+            columns: columns,
+            nonzeros: nonzeros,
+            format: Format::Column,
+            values: sorted,
+            indices: indices,
+            offsets: offsets,
+        };
+        matrix.coalesce();
+        matrix
+    }
+}
+
+impl<T: Element + Number> From<Coo<T>> for Compressed<T> {
+    #[inline]
+    fn from(coo: Coo<T>) -> Self {
+        (&coo).into()
+    }
+}
+
+impl<'l, T: Element> From<&'l Compressed<T>> for Coo<T> {
+    fn from(matrix: &'l Compressed<T>) -> Self {
+        let mut coo = Coo::with_capacity((matrix.rows, matrix.columns), matrix.nonzeros);
+        for (i, j, &value) in matrix.iter() {
+            coo.push(i, j, value);
+        }
+        coo
+    }
+}
+
+impl<T: Element> From<Compressed<T>> for Coo<T> {
+    #[inline]
+    fn from(matrix: Compressed<T>) -> Self {
+        (&matrix).into()
+    }
+}
+
+impl<T: Element + Number> Compressed<T> {
+    /// Sort the indices within each major and sum duplicate entries.
+    ///
+    /// This is used when building a matrix from a `Coo`, where repeated
+    /// `(i, j)` pairs are valid and must be accumulated together.
+    fn coalesce(&mut self) {
+        let majors = match self.format {
+            Format::Column => self.columns,
+            Format::Row => self.rows,
+        };
+
+        let mut write = 0;
+        for major in 0..majors {
+            let start = self.offsets[major];
+            let end = self.offsets[major + 1];
+
+            let mut run: Vec<(usize, T)> = self.indices[start..end].iter().cloned()
+                .zip(self.values[start..end].iter().cloned()).collect();
+            run.sort_by_key(|&(index, _)| index);
+
+            let mut run = run.into_iter();
+            self.offsets[major] = write;
+            if let Some((mut last_index, mut sum)) = run.next() {
+                for (index, value) in run {
+                    if index == last_index {
+                        sum = sum + value;
+                        continue;
+                    }
+                    self.indices[write] = last_index;
+                    self.values[write] = sum;
+                    write += 1;
+                    last_index = index;
+                    sum = value;
+                }
+                self.indices[write] = last_index;
+                self.values[write] = sum;
+                write += 1;
+            }
+        }
+        self.offsets[majors] = write;
+        self.indices.truncate(write);
+        self.values.truncate(write);
+        self.nonzeros = write;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use compressed::Format;
+    use {Coo, Compressed, Dense};
+
+    #[test]
+    fn push() {
+        let mut coo = Coo::new((3, 2));
+        coo.push(1, 0, 4.0);
+        coo.push(2, 1, 5.0);
+
+        assert_eq!(coo.len(), 2);
+        assert_eq!(&coo.row_indices, &[1, 2]);
+        assert_eq!(&coo.col_indices, &[0, 1]);
+        assert_eq!(&coo.values, &[4.0, 5.0]);
+    }
+
+    #[test]
+    fn into_compressed() {
+        let mut coo = Coo::new((3, 2));
+        coo.push(2, 0, 1.0);
+        coo.push(0, 1, 2.0);
+        coo.push(1, 0, 3.0);
+
+        let matrix: Compressed<_> = (&coo).into();
+        assert_eq!(matrix.nonzeros, 3);
+
+        let dense: Dense<_> = (&matrix).into();
+        assert_eq!(&*dense, &[0.0, 3.0, 1.0, 2.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn into_compressed_sums_duplicates() {
+        let mut coo = Coo::new((2, 2));
+        coo.push(0, 0, 1.0);
+        coo.push(0, 0, 2.0);
+        coo.push(1, 1, 5.0);
+
+        let matrix: Compressed<_> = (&coo).into();
+        assert_eq!(matrix.nonzeros, 2);
+        assert_eq!(matrix.format, Format::Column);
+
+        let dense: Dense<_> = (&matrix).into();
+        assert_eq!(&*dense, &[3.0, 0.0, 0.0, 5.0]);
+    }
+
+    #[test]
+    fn roundtrip() {
+        let dense = Dense::from_vec(vec![
+            0.0, 1.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 2.0, 3.0,
+            0.0, 0.0, 0.0, 0.0, 4.0,
+        ], (5, 3));
+
+        let matrix: Compressed<_> = (&dense).into();
+        let coo: Coo<_> = (&matrix).into();
+        let back: Compressed<_> = (&coo).into();
+
+        assert_eq!(matrix, back);
+    }
+}
@@ -0,0 +1,257 @@
+//! Matrix decompositions.
+//!
+//! These build directly on the LAPACK bindings already used by `sym_eig`:
+//! the input is copied into the output buffer, the LAPACK workspace is
+//! sized with a workspace-query call (`lwork = -1`), the real call is made,
+//! and the `info` flag is translated into a `Result`.
+
+use lapack;
+
+use Dense;
+
+/// The result of an LU decomposition with partial pivoting.
+pub struct Lu {
+    /// The unit lower-triangular factor.
+    pub l: Dense<f64>,
+    /// The upper-triangular factor.
+    pub u: Dense<f64>,
+    /// The pivot indices, one-based as returned by `dgetrf`.
+    pub pivots: Vec<i32>,
+}
+
+/// The result of a (thin) QR decomposition.
+pub struct Qr {
+    /// The orthogonal factor.
+    pub q: Dense<f64>,
+    /// The upper-triangular factor.
+    pub r: Dense<f64>,
+}
+
+/// The result of a Cholesky decomposition of a symmetric positive-definite
+/// matrix.
+pub struct Cholesky {
+    /// The lower-triangular factor such that `l * l^T` equals the original
+    /// matrix.
+    pub l: Dense<f64>,
+}
+
+/// The result of a singular value decomposition.
+pub struct Svd {
+    /// The left singular vectors.
+    pub u: Dense<f64>,
+    /// The singular values in descending order.
+    pub s: Vec<f64>,
+    /// The (transposed) right singular vectors.
+    pub vt: Dense<f64>,
+}
+
+impl Dense<f64> {
+    /// Compute the LU decomposition with partial pivoting via `dgetrf`.
+    pub fn lu(&self) -> Result<Lu, i32> {
+        let (m, n) = (self.rows, self.columns);
+        let mut a = self.values.clone();
+        let mut pivots = vec![0; m.min(n)];
+        let mut info = 0;
+
+        lapack::dgetrf(m, n, a.as_mut_slice(), m, pivots.as_mut_slice(), &mut info);
+        if info != 0 {
+            return Err(info);
+        }
+
+        let (l, u) = split_lu(&a, m, n);
+        Ok(Lu { l: l, u: u, pivots: pivots })
+    }
+
+    /// Compute the thin QR decomposition via `dgeqrf` followed by `dorgqr`.
+    pub fn qr(&self) -> Result<Qr, i32> {
+        let (m, n) = (self.rows, self.columns);
+        let k = m.min(n);
+        let mut a = self.values.clone();
+        let mut tau = vec![0.0; k];
+        let mut info = 0;
+
+        let mut probe = [0.0];
+        lapack::dgeqrf(m, n, a.as_mut_slice(), m, tau.as_mut_slice(), &mut probe, -1, &mut info);
+        let mut work = vec![0.0; probe[0] as usize];
+        lapack::dgeqrf(m, n, a.as_mut_slice(), m, tau.as_mut_slice(), &mut work, work.len() as i32,
+                       &mut info);
+        if info != 0 {
+            return Err(info);
+        }
+
+        let r = upper_triangular(&a, m, n);
+
+        // `dorgqr` only fills the leading `m * k` entries of its Q buffer,
+        // so when `n > m` (k == m) reusing the `m * n`-long `a` directly
+        // would leave `Qr.q.values` longer than `rows * columns`. Take
+        // exactly the first `k` columns instead.
+        let mut q = a[..(m * k)].to_vec();
+        let mut probe = [0.0];
+        lapack::dorgqr(m, k, k, q.as_mut_slice(), m, tau.as_slice(), &mut probe, -1, &mut info);
+        let mut work = vec![0.0; probe[0] as usize];
+        lapack::dorgqr(m, k, k, q.as_mut_slice(), m, tau.as_slice(), &mut work, work.len() as i32,
+                       &mut info);
+        if info != 0 {
+            return Err(info);
+        }
+
+        Ok(Qr { q: Dense { rows: m, columns: k, values: q }, r: r })
+    }
+
+    /// Compute the Cholesky decomposition of a symmetric positive-definite
+    /// matrix via `dpotrf`.
+    pub fn cholesky(&self) -> Result<Cholesky, i32> {
+        let m = self.rows;
+        debug_assert_eq!(m, self.columns);
+        let mut a = self.values.clone();
+        let mut info = 0;
+
+        lapack::dpotrf(b'L', m, a.as_mut_slice(), m, &mut info);
+        if info != 0 {
+            return Err(info);
+        }
+        zero_strict_upper(&mut a, m);
+
+        Ok(Cholesky { l: Dense { rows: m, columns: m, values: a } })
+    }
+
+    /// Compute the full singular value decomposition via `dgesdd`.
+    pub fn svd(&self) -> Result<Svd, i32> {
+        let (m, n) = (self.rows, self.columns);
+        let k = m.min(n);
+        let mut a = self.values.clone();
+        let mut s = vec![0.0; k];
+        let mut u = vec![0.0; m * m];
+        let mut vt = vec![0.0; n * n];
+        let mut iwork = vec![0; 8 * k];
+        let mut info = 0;
+
+        let mut probe = [0.0];
+        lapack::dgesdd(b'A', m, n, a.as_mut_slice(), m, s.as_mut_slice(), u.as_mut_slice(), m,
+                       vt.as_mut_slice(), n, &mut probe, -1, iwork.as_mut_slice(), &mut info);
+        let mut work = vec![0.0; probe[0] as usize];
+        lapack::dgesdd(b'A', m, n, a.as_mut_slice(), m, s.as_mut_slice(), u.as_mut_slice(), m,
+                       vt.as_mut_slice(), n, &mut work, work.len() as i32, iwork.as_mut_slice(),
+                       &mut info);
+        if info != 0 {
+            return Err(info);
+        }
+
+        Ok(Svd {
+            u: Dense { rows: m, columns: m, values: u },
+            s: s,
+            vt: Dense { rows: n, columns: n, values: vt },
+        })
+    }
+}
+
+/// Split the combined LU storage left behind by `dgetrf` in `a` (`m`-by-`n`,
+/// column-major) into the unit lower-triangular and upper-triangular
+/// factors.
+fn split_lu(a: &[f64], m: usize, n: usize) -> (Dense<f64>, Dense<f64>) {
+    let k = m.min(n);
+    let mut l = vec![0.0; m * k];
+    let mut u = vec![0.0; k * n];
+
+    for column in 0..k {
+        l[column * m + column] = 1.0;
+        for row in (column + 1)..m {
+            l[column * m + row] = a[column * m + row];
+        }
+    }
+    for column in 0..n {
+        for row in 0..k.min(column + 1) {
+            u[column * k + row] = a[column * m + row];
+        }
+    }
+
+    (Dense { rows: m, columns: k, values: l }, Dense { rows: k, columns: n, values: u })
+}
+
+/// Extract the upper-triangular part of the combined storage left behind by
+/// `dgeqrf` in `a` (`m`-by-`n`, column-major).
+fn upper_triangular(a: &[f64], m: usize, n: usize) -> Dense<f64> {
+    let k = m.min(n);
+    let mut r = vec![0.0; k * n];
+
+    for column in 0..n {
+        for row in 0..k.min(column + 1) {
+            r[column * k + row] = a[column * m + row];
+        }
+    }
+
+    Dense { rows: k, columns: n, values: r }
+}
+
+/// Zero out the strictly upper-triangular part of an `m`-by-`m`,
+/// column-major buffer, which `dpotrf` leaves untouched.
+fn zero_strict_upper(a: &mut [f64], m: usize) {
+    for column in 0..m {
+        for row in 0..column {
+            a[column * m + row] = 0.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use Dense;
+
+    #[test]
+    fn lu_square() {
+        let a = Dense { rows: 2, columns: 2, values: vec![4.0, 2.0, 7.0, 6.0] };
+
+        let lu = a.lu().unwrap();
+
+        assert_eq!((lu.l.rows, lu.l.columns), (2, 2));
+        assert_eq!((lu.u.rows, lu.u.columns), (2, 2));
+        assert_eq!(lu.pivots.len(), 2);
+    }
+
+    #[test]
+    fn qr_tall() {
+        // 3-by-2, more rows than columns: k == columns == 2.
+        let a = Dense { rows: 3, columns: 2, values: vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0] };
+
+        let qr = a.qr().unwrap();
+
+        assert_eq!((qr.q.rows, qr.q.columns), (3, 2));
+        assert_eq!(qr.q.values.len(), qr.q.rows * qr.q.columns);
+        assert_eq!((qr.r.rows, qr.r.columns), (2, 2));
+    }
+
+    #[test]
+    fn qr_wide() {
+        // 2-by-3, more columns than rows: k == rows == 2, the case that
+        // used to leave `q.values` sized `rows * columns_of_a` instead of
+        // `rows * k`.
+        let a = Dense { rows: 2, columns: 3, values: vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0] };
+
+        let qr = a.qr().unwrap();
+
+        assert_eq!((qr.q.rows, qr.q.columns), (2, 2));
+        assert_eq!(qr.q.values.len(), qr.q.rows * qr.q.columns);
+        assert_eq!((qr.r.rows, qr.r.columns), (2, 3));
+    }
+
+    #[test]
+    fn cholesky_square() {
+        let a = Dense { rows: 2, columns: 2, values: vec![4.0, 2.0, 2.0, 3.0] };
+
+        let cholesky = a.cholesky().unwrap();
+
+        assert_eq!((cholesky.l.rows, cholesky.l.columns), (2, 2));
+        assert_eq!(cholesky.l.values.len(), 4);
+    }
+
+    #[test]
+    fn svd_rectangular() {
+        let a = Dense { rows: 3, columns: 2, values: vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0] };
+
+        let svd = a.svd().unwrap();
+
+        assert_eq!((svd.u.rows, svd.u.columns), (3, 3));
+        assert_eq!(svd.s.len(), 2);
+        assert_eq!((svd.vt.rows, svd.vt.columns), (2, 2));
+    }
+}
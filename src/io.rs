@@ -0,0 +1,224 @@
+//! Matrix Market input and output.
+//!
+//! The [Matrix Market][1] format is a plain-text interchange format that
+//! many LAPACK/BLAS test corpora already use. This module reads and writes
+//! the `coordinate` variant, which maps onto `Compressed`, and the `array`
+//! variant, which maps onto `Dense`.
+//!
+//! This is the only Matrix Market reader/writer in the crate; an earlier,
+//! separate implementation against a since-deleted `format::{Compressed,
+//! Conventional}` tree was superseded by this one and removed rather than
+//! maintained in parallel.
+//!
+//! [1]: https://math.nist.gov/MatrixMarket/formats.html
+
+use std::fmt::Display;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::str::FromStr;
+
+use {Coo, Compressed, Dense, Element, Number};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Symmetry {
+    General,
+    Symmetric,
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn read_banner<R: BufRead>(lines: &mut io::Lines<R>) -> io::Result<(bool, Symmetry)> {
+    let line = lines.next().ok_or_else(|| invalid("missing Matrix Market banner"))??;
+    let mut tokens = line.split_whitespace();
+    if tokens.next() != Some("%%MatrixMarket") || tokens.next() != Some("matrix") {
+        return Err(invalid("unrecognized Matrix Market banner"));
+    }
+    let coordinate = match tokens.next() {
+        Some("coordinate") => true,
+        Some("array") => false,
+        _ => return Err(invalid("unsupported Matrix Market object")),
+    };
+    if tokens.next() != Some("real") {
+        return Err(invalid("only the `real` field is supported"));
+    }
+    let symmetry = match tokens.next() {
+        Some("general") | None => Symmetry::General,
+        Some("symmetric") => Symmetry::Symmetric,
+        _ => return Err(invalid("unsupported Matrix Market symmetry")),
+    };
+    Ok((coordinate, symmetry))
+}
+
+fn read_size_line<R: BufRead>(lines: &mut io::Lines<R>) -> io::Result<Vec<usize>> {
+    loop {
+        let line = lines.next().ok_or_else(|| invalid("missing size line"))??;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('%') {
+            continue;
+        }
+        return trimmed.split_whitespace()
+            .map(|token| token.parse().map_err(|_| invalid("malformed size line")))
+            .collect();
+    }
+}
+
+impl<T> Compressed<T> where T: Element + Number + FromStr, <T as FromStr>::Err: Display {
+    /// Read a matrix stored in the Matrix Market `coordinate` format.
+    ///
+    /// One-based indices are converted to zero-based, and `symmetric`
+    /// matrices have their off-diagonal entries mirrored.
+    pub fn read<R: Read>(reader: R) -> io::Result<Self> {
+        let mut lines = BufReader::new(reader).lines();
+        let (coordinate, symmetry) = read_banner(&mut lines)?;
+        if !coordinate {
+            return Err(invalid("expected a `coordinate` matrix"));
+        }
+
+        let size = read_size_line(&mut lines)?;
+        if size.len() != 3 {
+            return Err(invalid("malformed size line"));
+        }
+        let (rows, columns, nonzeros) = (size[0], size[1], size[2]);
+
+        let mut coo = Coo::with_capacity((rows, columns), nonzeros);
+        for line in lines {
+            let line = line?;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('%') {
+                continue;
+            }
+            let mut tokens = trimmed.split_whitespace();
+            let i: usize = tokens.next().ok_or_else(|| invalid("malformed entry"))?
+                .parse().map_err(|_| invalid("malformed row index"))?;
+            let j: usize = tokens.next().ok_or_else(|| invalid("malformed entry"))?
+                .parse().map_err(|_| invalid("malformed column index"))?;
+            let value: T = tokens.next().ok_or_else(|| invalid("malformed entry"))?
+                .parse().map_err(|error| invalid(&error.to_string()))?;
+
+            coo.push(i - 1, j - 1, value);
+            if symmetry == Symmetry::Symmetric && i != j {
+                coo.push(j - 1, i - 1, value);
+            }
+        }
+
+        Ok((&coo).into())
+    }
+
+    /// Write the matrix in the Matrix Market `coordinate` format.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> where T: Display {
+        writeln!(writer, "%%MatrixMarket matrix coordinate real general")?;
+        writeln!(writer, "{} {} {}", self.rows, self.columns, self.nonzeros)?;
+        for (i, j, value) in self.iter() {
+            writeln!(writer, "{} {} {}", i + 1, j + 1, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Dense<T> where T: Element + FromStr, <T as FromStr>::Err: Display {
+    /// Read a matrix stored in the Matrix Market `array` format.
+    pub fn read<R: Read>(reader: R) -> io::Result<Self> {
+        let mut lines = BufReader::new(reader).lines();
+        let (coordinate, _) = read_banner(&mut lines)?;
+        if coordinate {
+            return Err(invalid("expected an `array` matrix"));
+        }
+
+        let size = read_size_line(&mut lines)?;
+        if size.len() != 2 {
+            return Err(invalid("malformed size line"));
+        }
+        let (rows, columns) = (size[0], size[1]);
+
+        let mut matrix = Dense::new((rows, columns));
+        let mut k = 0;
+        for line in lines {
+            let line = line?;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('%') {
+                continue;
+            }
+            if k >= rows * columns {
+                return Err(invalid("too many entries for the declared size"));
+            }
+            matrix.values[k] = trimmed.parse().map_err(|error: T::Err| invalid(&error.to_string()))?;
+            k += 1;
+        }
+        if k != rows * columns {
+            return Err(invalid("too few entries for the declared size"));
+        }
+
+        Ok(matrix)
+    }
+
+    /// Write the matrix in the Matrix Market `array` format.
+    pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()> where T: Display {
+        writeln!(writer, "%%MatrixMarket matrix array real general")?;
+        writeln!(writer, "{} {}", self.rows, self.columns)?;
+        for value in self.values.iter() {
+            writeln!(writer, "{}", value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {Coo, Compressed, Dense};
+
+    #[test]
+    fn read_coordinate() {
+        let data = "%%MatrixMarket matrix coordinate real general\n\
+                     % a tiny example\n\
+                     3 3 2\n\
+                     1 1 1.0\n\
+                     3 2 5.0\n";
+
+        let matrix = Compressed::<f64>::read(data.as_bytes()).unwrap();
+        assert_eq!(matrix.rows, 3);
+        assert_eq!(matrix.columns, 3);
+        assert_eq!(matrix.nonzeros, 2);
+        assert_eq!(matrix.get((0, 0)), 1.0);
+        assert_eq!(matrix.get((2, 1)), 5.0);
+    }
+
+    #[test]
+    fn read_coordinate_symmetric() {
+        let data = "%%MatrixMarket matrix coordinate real symmetric\n\
+                     3 3 1\n\
+                     3 1 7.0\n";
+
+        let matrix = Compressed::<f64>::read(data.as_bytes()).unwrap();
+        assert_eq!(matrix.nonzeros, 2);
+        assert_eq!(matrix.get((2, 0)), 7.0);
+        assert_eq!(matrix.get((0, 2)), 7.0);
+    }
+
+    #[test]
+    fn write_coordinate_roundtrip() {
+        let mut coo = Coo::new((2, 2));
+        coo.push(0, 0, 1.0);
+        coo.push(1, 1, 2.0);
+        let matrix: Compressed<f64> = (&coo).into();
+
+        let mut buffer = Vec::new();
+        matrix.write(&mut buffer).unwrap();
+
+        let back = Compressed::<f64>::read(&buffer[..]).unwrap();
+        assert_eq!(matrix, back);
+    }
+
+    #[test]
+    fn read_array() {
+        let data = "%%MatrixMarket matrix array real general\n\
+                     2 2\n\
+                     1.0\n\
+                     2.0\n\
+                     3.0\n\
+                     4.0\n";
+
+        let matrix = Dense::<f64>::read(data.as_bytes()).unwrap();
+        assert_eq!(&*matrix, &[1.0, 2.0, 3.0, 4.0]);
+    }
+}
@@ -3,6 +3,19 @@
 extern crate blas;
 extern crate lapack;
 
+pub mod band;
+pub mod compressed;
+pub mod coo;
+pub mod decompose;
+pub mod io;
+
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
+pub use band::BandMatrix;
+pub use compressed::Compressed;
+pub use coo::Coo;
+
 /// Multiplies an m-by-p matrix `a` by a p-by-n matrix `b` and stores the
 /// result in an m-by-n matrix `c`.
 #[inline]
@@ -0,0 +1,101 @@
+//! Property-based testing strategies, gated behind the `proptest` feature.
+//!
+//! These generate well-formed `Dense`, `Compressed`, and `BandMatrix`
+//! values so that downstream crates can property-test round-trips such as
+//! `Dense -> Compressed -> Dense` and `transpose` idempotence across the
+//! whole shape space instead of a handful of hand-picked fixtures.
+//!
+//! Because the underlying `Strategy` implementations (ranges, `Vec`,
+//! `HashSet`) already shrink toward their smaller values, `dense` matrices
+//! shrink toward smaller dimensions and `compressed` matrices toward fewer
+//! nonzeros for free. The shapes produced by `compressed` and `band_matrix`
+//! are fixed by their arguments and do not shrink.
+
+use proptest::collection::{hash_set, vec};
+use proptest::prelude::*;
+
+use compressed::Format;
+use {BandMatrix, Compressed, Dense, Element};
+
+/// A strategy producing `Dense<T>` matrices with dimensions drawn from
+/// `rows` and `columns` and values drawn from `value`.
+pub fn dense<T, R, C, S>(rows: R, columns: C, value: S) -> impl Strategy<Value = Dense<T>>
+where
+    T: Element,
+    R: Strategy<Value = usize>,
+    C: Strategy<Value = usize>,
+    S: Strategy<Value = T> + Clone,
+{
+    (rows, columns).prop_flat_map(move |(rows, columns)| {
+        vec(value.clone(), rows * columns)
+            .prop_map(move |values| Dense::from_vec(values, (rows, columns)))
+    })
+}
+
+/// A strategy producing well-formed `Compressed<T>` matrices of the given
+/// shape, with at most `ceil(rows * density)` of each column's rows
+/// populated.
+///
+/// The generated `offsets` are monotonically non-decreasing, the `indices`
+/// within each column are strictly sorted, and `nonzeros` is always
+/// consistent with `values.len()` — the `debug_valid!` invariant always
+/// holds.
+pub fn compressed<T, S>(
+    rows: usize,
+    columns: usize,
+    density: f64,
+    value: S,
+) -> impl Strategy<Value = Compressed<T>>
+where
+    T: Element,
+    S: Strategy<Value = T> + Clone,
+{
+    let per_column = ((rows as f64) * density).ceil().max(0.0) as usize;
+    let per_column = per_column.min(rows);
+
+    vec(hash_set(0..rows, 0..=per_column), columns).prop_flat_map(move |columns_of_rows| {
+        let mut offsets = Vec::with_capacity(columns_of_rows.len() + 1);
+        let mut indices = Vec::new();
+        offsets.push(0);
+        for rows_in_column in &columns_of_rows {
+            let mut sorted: Vec<usize> = rows_in_column.iter().cloned().collect();
+            sorted.sort();
+            indices.extend(sorted);
+            offsets.push(indices.len());
+        }
+
+        let nonzeros = indices.len();
+        vec(value.clone(), nonzeros).prop_map(move |values| Compressed {
+            rows: rows,
+            columns: columns,
+            nonzeros: nonzeros,
+            format: Format::Column,
+            values: values,
+            indices: indices.clone(),
+            offsets: offsets.clone(),
+        })
+    })
+}
+
+/// A strategy producing `BandMatrix<T>` matrices with the given shape and
+/// bandwidth, and values drawn from `value`.
+pub fn band_matrix<T, S>(
+    rows: usize,
+    columns: usize,
+    subdiagonals: usize,
+    superdiagonals: usize,
+    value: S,
+) -> impl Strategy<Value = BandMatrix<T>>
+where
+    T: Element,
+    S: Strategy<Value = T> + Clone,
+{
+    let diagonals = subdiagonals + 1 + superdiagonals;
+    vec(value, diagonals * columns).prop_map(move |data| BandMatrix {
+        rows: rows,
+        columns: columns,
+        superdiagonals: superdiagonals,
+        subdiagonals: subdiagonals,
+        data: data,
+    })
+}